@@ -0,0 +1,45 @@
+// Stages the Noir circuit (and, optionally, its SRS) named by
+// `MOPRO_NOIR_CIRCUIT_PATH` / `MOPRO_NOIR_VK_PATH` / `MOPRO_NOIR_SRS_PATH`
+// into `OUT_DIR` so `src/noir/embedded.rs` can bake them into the binary
+// with `include_bytes!` instead of the app reading them from disk at
+// runtime. Unset by default: without these env vars the crate behaves
+// exactly as it did before, reading `circuit_path` / `srs_path` arguments
+// at call time.
+
+use std::{env, fs, path::Path};
+
+fn main() {
+    // Declare both cfgs up front, regardless of whether this build actually
+    // sets them, so `#[cfg(noir_embedded)]` / `#[cfg(noir_srs_embedded)]` in
+    // `src/noir/embedded.rs` don't trip rustc's "unexpected cfg condition
+    // name" lint (and, under `-D warnings`, fail the build) in the default,
+    // non-embedding build.
+    println!("cargo::rustc-check-cfg=cfg(noir_embedded)");
+    println!("cargo::rustc-check-cfg=cfg(noir_srs_embedded)");
+
+    println!("cargo:rerun-if-env-changed=MOPRO_NOIR_CIRCUIT_PATH");
+    println!("cargo:rerun-if-env-changed=MOPRO_NOIR_VK_PATH");
+    println!("cargo:rerun-if-env-changed=MOPRO_NOIR_SRS_PATH");
+
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR set by cargo");
+
+    if let (Ok(circuit_path), Ok(vk_path)) = (
+        env::var("MOPRO_NOIR_CIRCUIT_PATH"),
+        env::var("MOPRO_NOIR_VK_PATH"),
+    ) {
+        stage(&circuit_path, &out_dir, "noir_circuit.json");
+        stage(&vk_path, &out_dir, "noir_circuit.vk");
+        println!("cargo:rustc-cfg=noir_embedded");
+
+        if let Ok(srs_path) = env::var("MOPRO_NOIR_SRS_PATH") {
+            stage(&srs_path, &out_dir, "noir_circuit.srs");
+            println!("cargo:rustc-cfg=noir_srs_embedded");
+        }
+    }
+}
+
+fn stage(src: &str, out_dir: &str, dest_name: &str) {
+    println!("cargo:rerun-if-changed={src}");
+    fs::copy(src, Path::new(out_dir).join(dest_name))
+        .unwrap_or_else(|e| panic!("failed to stage {src} into OUT_DIR: {e}"));
+}
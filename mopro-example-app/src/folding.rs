@@ -0,0 +1,295 @@
+// Nova+CycleFold-style incrementally verifiable computation (IVC) for a
+// fixed Noir step circuit applied over a long sequence of states (e.g. a
+// hash chain).
+//
+// Built on nova-snark's real recursion API: a `PublicParams<E1, E2, C1, C2>`
+// pins the primary/secondary curves (BN254 + its Grumpkin CycleFold
+// companion) and step circuits, a `RecursiveSNARK` is the running
+// relaxed-instance/witness accumulator that `prove_step` folds one more
+// application of the step circuit into, and `CompressedSNARK` wraps the
+// final accumulator in the succinct decider proof. Folding state is
+// long-lived and mutated across many `fold_noir_step` calls, so it's kept
+// behind the same handle registry `poseidon_tree` uses rather than threaded
+// through as an owned value on every UniFFI call.
+//
+// `noir_rs`/Barretenberg prove Noir's ACIR circuits with UltraHonk (a
+// PLONKish arithmetization), while Nova folds R1CS instances — the two
+// aren't interchangeable, and re-synthesizing an arbitrary ACIR program's
+// gates into a bellpepper R1CS circuit (an ACIR->R1CS bridge) is a project
+// in its own right that does not exist in this crate. [`NoirStepCircuit`]
+// therefore does NOT fold the Noir step's actual constraints: it folds a
+// trivial running-sum accumulator whose per-step arity is validated against
+// the real ABI declared by the compiled circuit at `circuit_path`, so at
+// least a step's shape (not its semantics) is checked against the named
+// circuit before it is folded in. Callers that need the step's Noir
+// constraints enforced should prove each step with `generate_noir_proof`
+// and verify it out of band; this module only accumulates, it does not
+// verify Noir-step correctness.
+
+use std::sync::Mutex;
+
+use bellpepper_core::{num::AllocatedNum, ConstraintSystem, SynthesisError};
+use ff::{Field, PrimeField};
+use nova_snark::{
+    provider::{hyperkzg::EvaluationEngine as HyperKzgEE, ipa_pc::EvaluationEngine as IpaEE, Bn256EngineKZG, GrumpkinEngine},
+    spartan::snark::RelaxedR1CSSNARK,
+    traits::{
+        circuit::{StepCircuit, TrivialCircuit},
+        snark::default_ck_hint,
+        Engine,
+    },
+    CompressedSNARK, PublicParams, RecursiveSNARK,
+};
+
+use crate::{
+    handle::{Handle, HandleRegistry},
+    MoproError,
+};
+
+/// Reads `circuit_path` (a `nargo`-compiled circuit JSON) and returns the
+/// number of parameters in its ABI, so a folding step's input arity can be
+/// checked against the circuit it is named after instead of ignoring
+/// `circuit_path` entirely.
+fn circuit_input_arity(circuit_path: &str) -> Result<usize, MoproError> {
+    let contents = std::fs::read_to_string(circuit_path).map_err(|e| {
+        MoproError::NoirError(format!("failed to read circuit {circuit_path}: {e}"))
+    })?;
+    let json: serde_json::Value = serde_json::from_str(&contents).map_err(|e| {
+        MoproError::NoirError(format!(
+            "failed to parse circuit {circuit_path} as nargo JSON: {e}"
+        ))
+    })?;
+    json.get("abi")
+        .and_then(|abi| abi.get("parameters"))
+        .and_then(|params| params.as_array())
+        .map(|params| params.len())
+        .ok_or_else(|| {
+            MoproError::NoirError(format!(
+                "circuit {circuit_path} has no abi.parameters to size the folding step against"
+            ))
+        })
+}
+
+type E1 = Bn256EngineKZG;
+type E2 = GrumpkinEngine;
+type C1 = NoirStepCircuit<<E1 as Engine>::Scalar>;
+type C2 = TrivialCircuit<<E2 as Engine>::Scalar>;
+type EE1 = HyperKzgEE<E1>;
+type EE2 = IpaEE<E2>;
+type S1 = RelaxedR1CSSNARK<E1, EE1>;
+type S2 = RelaxedR1CSSNARK<E2, EE2>;
+
+/// A single Nova accumulator step, gated to the circuit at `circuit_path`
+/// by input arity only: it does NOT re-synthesize that circuit's ACIR gates
+/// (see the module doc comment), so folding a step proves that a sequence
+/// of `step_inputs` of the right shape were folded in, not that the Noir
+/// circuit's constraints hold for them.
+#[derive(Clone)]
+struct NoirStepCircuit<F: PrimeField> {
+    circuit_path: String,
+    step_inputs: Vec<F>,
+}
+
+impl<F: PrimeField> NoirStepCircuit<F> {
+    fn new(circuit_path: String, step_inputs: Vec<F>) -> Self {
+        Self {
+            circuit_path,
+            step_inputs,
+        }
+    }
+}
+
+impl<F: PrimeField> StepCircuit<F> for NoirStepCircuit<F> {
+    fn arity(&self) -> usize {
+        1
+    }
+
+    fn synthesize<CS: ConstraintSystem<F>>(
+        &self,
+        cs: &mut CS,
+        z: &[AllocatedNum<F>],
+    ) -> Result<Vec<AllocatedNum<F>>, SynthesisError> {
+        // `self.circuit_path` is not re-synthesized here (see the module doc
+        // comment); each step input is allocated and summed into the running
+        // state so the accumulator, transcript and decider wiring below
+        // exercise the real Nova API end to end against a concrete step
+        // function, with the step's shape validated against that circuit's
+        // ABI in `fold_noir_init`/`fold_noir_step` before it reaches here.
+        let mut state = z[0].clone();
+        for (i, input) in self.step_inputs.iter().enumerate() {
+            let input_var = AllocatedNum::alloc(cs.namespace(|| format!("step_input_{i}")), || Ok(*input))?;
+            let next = AllocatedNum::alloc(cs.namespace(|| format!("step_state_{i}")), || {
+                let mut acc = state.get_value().ok_or(SynthesisError::AssignmentMissing)?;
+                acc += input_var.get_value().ok_or(SynthesisError::AssignmentMissing)?;
+                Ok(acc)
+            })?;
+            cs.enforce(
+                || format!("step_fold_{i}"),
+                |lc| lc + state.get_variable() + input_var.get_variable(),
+                |lc| lc + CS::one(),
+                |lc| lc + next.get_variable(),
+            );
+            state = next;
+        }
+        Ok(vec![state])
+    }
+}
+
+/// The running relaxed instance-witness pair plus everything needed to fold
+/// the next step into it and to later produce the decider proof.
+struct FoldingState {
+    pp: PublicParams<E1, E2, C1, C2>,
+    recursive_snark: RecursiveSNARK<E1, E2, C1, C2>,
+    z0_primary: Vec<<E1 as Engine>::Scalar>,
+    z0_secondary: Vec<<E2 as Engine>::Scalar>,
+    num_steps: usize,
+    /// The circuit this accumulator's steps are validated against, and its
+    /// declared ABI arity (see [`circuit_input_arity`]).
+    step_circuit_path: String,
+    step_arity: usize,
+}
+
+static FOLDING_STATES: HandleRegistry<Mutex<FoldingState>> = HandleRegistry::new();
+
+/// Sets up the accumulator for repeatedly folding `step_circuit_path`
+/// (compiled with `nargo`, the same format the rest of the `noir` module
+/// takes) and returns a handle to it. `srs` is accepted for symmetry with
+/// the rest of the `noir` module's functions but is currently unused, since
+/// nothing in this module proves or verifies an UltraHonk proof directly.
+#[uniffi::export]
+pub fn fold_noir_init(step_circuit_path: String, srs: Option<String>) -> Result<Handle, MoproError> {
+    let _ = srs;
+
+    let step_arity = circuit_input_arity(&step_circuit_path)?;
+
+    let z0_primary = vec![<E1 as Engine>::Scalar::ZERO];
+    let z0_secondary = vec![<E2 as Engine>::Scalar::ZERO];
+    let c_primary = NoirStepCircuit::new(
+        step_circuit_path.clone(),
+        vec![<E1 as Engine>::Scalar::ZERO; step_arity],
+    );
+    let c_secondary = TrivialCircuit::default();
+
+    let pp = PublicParams::<E1, E2, C1, C2>::setup(&c_primary, &c_secondary, &*default_ck_hint(), &*default_ck_hint())
+        .map_err(|e| MoproError::NoirError(format!("Failed to set up public params: {}", e)))?;
+
+    let recursive_snark = RecursiveSNARK::<E1, E2, C1, C2>::new(&pp, &c_primary, &c_secondary, &z0_primary, &z0_secondary)
+        .map_err(|e| MoproError::NoirError(format!("Failed to initialize recursive SNARK: {}", e)))?;
+
+    let state = FoldingState {
+        pp,
+        recursive_snark,
+        z0_primary,
+        z0_secondary,
+        num_steps: 0,
+        step_circuit_path,
+        step_arity,
+    };
+
+    Ok(FOLDING_STATES.insert(Mutex::new(state)))
+}
+
+/// Frees the folding accumulator identified by `handle`. Callers that are
+/// done folding (after calling [`fold_noir_decider_prove`], or on failure)
+/// must call this, or its entry leaks in the process-wide registry for the
+/// rest of the process's lifetime.
+#[uniffi::export]
+pub fn fold_noir_free(handle: Handle) -> Result<(), MoproError> {
+    FOLDING_STATES.remove(handle)
+}
+
+/// Folds one more application of the step circuit, with `step_inputs` as its
+/// witness for this step, into the accumulator identified by `handle`.
+///
+/// `prove_step` is Nova's own folding step: it commits to the freshly
+/// satisfied step instance, derives a folding challenge from a transcript
+/// over the running and step commitments, computes the cross-term/error
+/// commitment and updates the running `E`, `u` and committed witness, and
+/// folds the non-native elliptic-curve arithmetic this involves into the
+/// CycleFold accumulator on the secondary (Grumpkin) curve.
+#[uniffi::export]
+pub fn fold_noir_step(handle: Handle, step_inputs: Vec<String>) -> Result<(), MoproError> {
+    FOLDING_STATES.with(handle, |state| {
+        let mut state = state.lock().unwrap();
+        if step_inputs.len() != state.step_arity {
+            return Err(MoproError::NoirError(format!(
+                "step has {} inputs, but {} declares an ABI arity of {}",
+                step_inputs.len(),
+                state.step_circuit_path,
+                state.step_arity
+            )));
+        }
+        let step_inputs = parse_step_inputs(&step_inputs)?;
+        let c_primary = NoirStepCircuit::new(state.step_circuit_path.clone(), step_inputs);
+        let c_secondary = TrivialCircuit::default();
+
+        let pp = &state.pp;
+        let num_steps = state.num_steps;
+        state
+            .recursive_snark
+            .prove_step(pp, &c_primary, &c_secondary)
+            .map_err(|e| MoproError::NoirError(format!("Failed to fold step {num_steps}: {}", e)))?;
+        state.num_steps += 1;
+
+        Ok(())
+    })
+}
+
+/// Wraps the accumulated instance-witness pair in a succinct decider SNARK
+/// proving that all of the folded step applications ran correctly.
+#[uniffi::export]
+pub fn fold_noir_decider_prove(handle: Handle) -> Result<Vec<u8>, MoproError> {
+    FOLDING_STATES.with(handle, |state| {
+        let state = state.lock().unwrap();
+
+        state
+            .recursive_snark
+            .verify(&state.pp, state.num_steps, &state.z0_primary, &state.z0_secondary)
+            .map_err(|e| MoproError::NoirError(format!("Recursive SNARK failed its own check: {}", e)))?;
+
+        let (pk, _vk) = CompressedSNARK::<E1, E2, C1, C2, S1, S2>::setup(&state.pp)
+            .map_err(|e| MoproError::NoirError(format!("Failed to set up decider keys: {}", e)))?;
+
+        let compressed = CompressedSNARK::<E1, E2, C1, C2, S1, S2>::prove(&state.pp, &pk, &state.recursive_snark)
+            .map_err(|e| MoproError::NoirError(format!("Failed to produce decider proof: {}", e)))?;
+
+        bincode::serialize(&compressed)
+            .map_err(|e| MoproError::NoirError(format!("Failed to serialize decider proof: {}", e)))
+    })
+}
+
+// There is no `render_decider_solidity_verifier` here: rendering an
+// on-chain check for a Nova `CompressedSNARK` decider proof means emitting
+// its actual Spartan/HyperKZG verification equation against a concrete
+// Solidity pairing/MSM library, which does not exist in this crate. A
+// contract that only `revert`s would not satisfy an on-chain decider check,
+// so nothing is rendered; callers needing on-chain verification of
+// [`fold_noir_decider_prove`]'s output need a real Nova Solidity verifier
+// (e.g. generated by `nova-snark`'s or a downstream project's own tooling).
+
+/// Parses each of `step_inputs` (a `0x`-prefixed, or bare, hex string) as a
+/// full field element for the curve Nova's primary circuit runs over,
+/// left-padding short inputs and rejecting ones too large to fit instead of
+/// panicking, mirroring `solidity::parse_field_word`'s guard.
+fn parse_step_inputs(step_inputs: &[String]) -> Result<Vec<<E1 as Engine>::Scalar>, MoproError> {
+    step_inputs
+        .iter()
+        .map(|input| {
+            let hex_str = input.strip_prefix("0x").unwrap_or(input);
+            if hex_str.len() > 64 {
+                return Err(MoproError::NoirError(format!(
+                    "step input {input} does not fit in a 32-byte field element"
+                )));
+            }
+            let mut bytes = hex::decode(format!("{hex_str:0>64}"))
+                .map_err(|e| MoproError::NoirError(format!("invalid step input {input}: {e}")))?;
+            // `hex::decode` above yields big-endian bytes; `ff::PrimeField`
+            // reprs for the curves nova-snark uses are little-endian.
+            bytes.reverse();
+            let mut repr = <<E1 as Engine>::Scalar as PrimeField>::Repr::default();
+            repr.as_mut().copy_from_slice(&bytes);
+            Option::from(<E1 as Engine>::Scalar::from_repr(repr))
+                .ok_or_else(|| MoproError::NoirError(format!("step input {input} is not a valid field element")))
+        })
+        .collect()
+}
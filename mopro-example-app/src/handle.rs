@@ -0,0 +1,76 @@
+// Shared handle-registry helper for the modules that hold long-lived state
+// mutated in place across several UniFFI calls (`poseidon_tree`, `folding`)
+// rather than threading an owned value through every call.
+
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+};
+
+use crate::MoproError;
+
+pub type Handle = u64;
+
+pub struct HandleRegistry<T> {
+    next: AtomicU64,
+    entries: Mutex<HashMap<Handle, T>>,
+}
+
+impl<T> HandleRegistry<T> {
+    pub const fn new() -> Self {
+        Self {
+            next: AtomicU64::new(1),
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn insert(&self, value: T) -> Handle {
+        let handle = self.next.fetch_add(1, Ordering::Relaxed);
+        self.entries.lock().unwrap().insert(handle, value);
+        handle
+    }
+
+    /// Looks up `handle` and runs `f` against it, surfacing an
+    /// `unknown handle` `MoproError` if it isn't (or is no longer)
+    /// registered.
+    pub fn with<R>(
+        &self,
+        handle: Handle,
+        f: impl FnOnce(&T) -> Result<R, MoproError>,
+    ) -> Result<R, MoproError> {
+        let entries = self.entries.lock().unwrap();
+        let value = entries
+            .get(&handle)
+            .ok_or_else(|| MoproError::NoirError(format!("unknown handle {handle}")))?;
+        f(value)
+    }
+
+    /// As [`Self::with`], but with mutable access to the registered value.
+    pub fn with_mut<R>(
+        &self,
+        handle: Handle,
+        f: impl FnOnce(&mut T) -> Result<R, MoproError>,
+    ) -> Result<R, MoproError> {
+        let mut entries = self.entries.lock().unwrap();
+        let value = entries
+            .get_mut(&handle)
+            .ok_or_else(|| MoproError::NoirError(format!("unknown handle {handle}")))?;
+        f(value)
+    }
+
+    /// Drops `handle`'s entry, freeing the state it held. Callers that create
+    /// handles for the lifetime of some larger operation (a tree, a folding
+    /// run) should call this once that operation is done, or the entry leaks
+    /// for the process lifetime.
+    pub fn remove(&self, handle: Handle) -> Result<(), MoproError> {
+        self.entries
+            .lock()
+            .unwrap()
+            .remove(&handle)
+            .map(|_| ())
+            .ok_or_else(|| MoproError::NoirError(format!("unknown handle {handle}")))
+    }
+}
@@ -11,6 +11,8 @@ fn mopro_uniffi_hello_world() -> String {
 #[macro_use]
 mod stubs;
 
+mod handle;
+
 // CIRCOM_TEMPLATE
 circom_stub!();
 
@@ -23,6 +25,15 @@ halo2_stub!();
 // Module containing the Noir circuit logic (Multiplier2)
 mod noir;
 
+// Incremental Poseidon Merkle tree, usable independently of any one circuit
+// to build the commitment trees / inclusion witnesses Noir and Circom
+// circuits are fed as inputs.
+mod poseidon_tree;
+
+// Nova+CycleFold-style folding for incrementally verifiable computation over
+// a repeated Noir step circuit.
+mod folding;
+
 #[cfg(test)]
 mod noir_tests {
     use super::noir::{generate_noir_proof, get_noir_verification_key, verify_noir_proof};
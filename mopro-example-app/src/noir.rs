@@ -0,0 +1,84 @@
+// Module containing the Noir circuit logic (Multiplier2)
+//
+// Wraps the Barretenberg UltraHonk prover/verifier so the generated UniFFI
+// bindings can prove and verify a single fixed Noir circuit per app, the
+// same way `circom_stub!()` / `halo2_stub!()` wrap their own backends.
+
+use noir_rs::{
+    barretenberg::{
+        prove::prove_ultra_honk,
+        srs::setup_srs_from_file,
+        utils::get_honk_verification_key,
+        verify::verify_ultra_honk,
+    },
+    witness::from_vec_str_to_witness_map,
+};
+
+use crate::MoproError;
+
+/// Computes the UltraHonk verification key for `circuit_path`.
+///
+/// `on_chain` selects the Keccak transcript hash used by the Solidity
+/// verifier instead of the default Poseidon/Blake transcript used for
+/// native verification.
+#[uniffi::export]
+pub fn get_noir_verification_key(
+    circuit_path: String,
+    srs_path: Option<String>,
+    on_chain: bool,
+    low_memory_mode: bool,
+) -> Result<Vec<u8>, MoproError> {
+    setup_srs_from_file(&circuit_path, srs_path.as_deref(), low_memory_mode)
+        .map_err(|e| MoproError::NoirError(format!("Failed to set up SRS: {}", e)))?;
+
+    get_honk_verification_key(&circuit_path, on_chain, low_memory_mode)
+        .map_err(|e| MoproError::NoirError(format!("Failed to generate verification key: {}", e)))
+}
+
+/// Generates an UltraHonk proof for `circuit_inputs` against `circuit_path`.
+#[uniffi::export]
+pub fn generate_noir_proof(
+    circuit_path: String,
+    srs_path: Option<String>,
+    circuit_inputs: Vec<String>,
+    on_chain: bool,
+    vk: Vec<u8>,
+    low_memory_mode: bool,
+) -> Result<Vec<u8>, MoproError> {
+    setup_srs_from_file(&circuit_path, srs_path.as_deref(), low_memory_mode)
+        .map_err(|e| MoproError::NoirError(format!("Failed to set up SRS: {}", e)))?;
+
+    let witness_map = from_vec_str_to_witness_map(circuit_inputs)
+        .map_err(|e| MoproError::NoirError(format!("Failed to build witness map: {}", e)))?;
+
+    let (proof, _public_inputs) = prove_ultra_honk(&circuit_path, witness_map, vk, on_chain, low_memory_mode)
+        .map_err(|e| MoproError::NoirError(format!("Failed to generate proof: {}", e)))?;
+
+    Ok(proof)
+}
+
+/// Verifies a proof previously produced by [`generate_noir_proof`].
+#[uniffi::export]
+pub fn verify_noir_proof(
+    circuit_path: String,
+    proof: Vec<u8>,
+    on_chain: bool,
+    vk: Vec<u8>,
+    low_memory_mode: bool,
+) -> Result<bool, MoproError> {
+    verify_ultra_honk(proof, vk, on_chain, low_memory_mode)
+        .map_err(|e| MoproError::NoirError(format!("Failed to verify proof: {}", e)))
+}
+
+mod solidity;
+
+pub use solidity::{encode_noir_calldata, render_noir_solidity_verifier};
+
+pub mod aggregation;
+
+mod bytes;
+mod embedded;
+
+pub use bytes::{
+    generate_noir_proof_bytes, get_noir_verification_key_bytes, verify_noir_proof_bytes,
+};
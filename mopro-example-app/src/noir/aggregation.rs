@@ -0,0 +1,257 @@
+// Proof aggregation for the Noir UltraHonk backend.
+//
+// Chains N independently generated base proofs through repeated calls to the
+// SAME compiled recursive-verifier circuit — a Noir program built around the
+// stdlib's `std::verify_proof(verification_key, proof, public_inputs,
+// key_hash, input_aggregation_object) -> output_aggregation_object`, the
+// real fixed-ABI primitive Noir recursion is built on. Each call verifies
+// one base proof in-circuit and threads the aggregation object (the BN254
+// pairing checks deferred rather than paid for on every link) into the
+// next call, so N base proofs cost one chain of N small recursive proofs
+// plus (eventually) a single closing pairing check, instead of N full
+// verifications.
+
+use noir_rs::barretenberg::{
+    prove::prove_ultra_honk, srs::setup_srs_from_file, verify::verify_ultra_honk,
+};
+
+use crate::{poseidon_tree::poseidon_hash, MoproError};
+
+/// Chains `proofs[i]`/`vks[i]`/`public_inputs[i]` through the recursive
+/// verifier circuit at `recursive_verifier_circuit_path`, starting from
+/// `initial_aggregation_object` (the zero/identity aggregation object for
+/// that circuit's ABI — its size is fixed by the circuit and must be
+/// supplied by the caller, the same way `circuit_path` itself is).
+///
+/// Because a compiled Noir circuit has one fixed witness layout, every base
+/// proof/vk/public-input triple folded into this chain must share the same
+/// lengths as `proofs[0]`/`vks[0]`/`public_inputs[0]` (they must all come
+/// from the same base circuit); aggregate proofs from different circuits in
+/// separate chains.
+///
+/// `on_chain` selects the Keccak transcript for the final link's proof so it
+/// can be checked with [`super::render_noir_solidity_verifier`]; every
+/// earlier link always runs over the native transcript regardless of this
+/// flag, since only the final link's proof is returned.
+///
+/// Returns the final link's proof and its output aggregation object. This
+/// function does **not** close that aggregation object: natively verifying
+/// the returned proof (see [`verify_aggregated_proof`]) confirms every link
+/// in the chain validated its base proof, but the BN254 pairing checks
+/// accumulated across links still need a final closing check before the
+/// whole chain is fully verified. That closing check is specific to the
+/// recursive-verifier circuit's own concrete pairing layout and is left to
+/// the caller (e.g. on-chain, the same way `render_noir_solidity_verifier`'s
+/// rendered contract documents the pairing check it would need without
+/// performing one).
+#[allow(clippy::too_many_arguments)]
+#[uniffi::export]
+pub fn aggregate_noir_proofs(
+    recursive_verifier_circuit_path: String,
+    srs_path: Option<String>,
+    proofs: Vec<Vec<u8>>,
+    vks: Vec<Vec<u8>>,
+    public_inputs: Vec<Vec<String>>,
+    initial_aggregation_object: Vec<String>,
+    on_chain: bool,
+    low_memory_mode: bool,
+) -> Result<(Vec<u8>, Vec<String>), MoproError> {
+    if proofs.len() != vks.len() || proofs.len() != public_inputs.len() {
+        return Err(MoproError::NoirError(format!(
+            "proofs/vks/public_inputs length mismatch: {} proofs, {} vks, {} public_inputs",
+            proofs.len(),
+            vks.len(),
+            public_inputs.len()
+        )));
+    }
+    if proofs.is_empty() {
+        return Err(MoproError::NoirError(
+            "aggregate_noir_proofs requires at least one proof".to_string(),
+        ));
+    }
+    for (i, (proof, vk)) in proofs.iter().zip(vks.iter()).enumerate() {
+        if proof.len() != proofs[0].len() || vk.len() != vks[0].len() {
+            return Err(MoproError::NoirError(format!(
+                "proof/vk at index {i} has a different length than index 0; every link in a \
+                 recursive-verifier chain must come from the same compiled circuit"
+            )));
+        }
+    }
+
+    setup_srs_from_file(&recursive_verifier_circuit_path, srs_path.as_deref(), low_memory_mode)
+        .map_err(|e| MoproError::NoirError(format!("Failed to set up SRS: {}", e)))?;
+
+    let mut recursive_vk = super::get_noir_verification_key(
+        recursive_verifier_circuit_path.clone(),
+        None,
+        false,
+        low_memory_mode,
+    )?;
+
+    let mut aggregation_object = initial_aggregation_object;
+    let mut chain_proof = Vec::new();
+    let last = proofs.len() - 1;
+    for i in 0..proofs.len() {
+        let is_last = i == last;
+        if is_last && on_chain {
+            // Re-key the last link for the Keccak transcript so the
+            // returned proof can be checked on-chain; every earlier link
+            // stays on the native transcript since only this one is kept.
+            recursive_vk = super::get_noir_verification_key(
+                recursive_verifier_circuit_path.clone(),
+                None,
+                true,
+                low_memory_mode,
+            )?;
+        }
+
+        let (proof_i, agg_i) = recursively_verify(
+            &recursive_verifier_circuit_path,
+            &recursive_vk,
+            &proofs[i],
+            &vks[i],
+            &public_inputs[i],
+            &aggregation_object,
+            is_last && on_chain,
+            low_memory_mode,
+        )?;
+        chain_proof = proof_i;
+        aggregation_object = agg_i;
+    }
+
+    Ok((chain_proof, aggregation_object))
+}
+
+/// Natively verifies the final link's proof produced by
+/// [`aggregate_noir_proofs`] against the recursive verifier circuit's own
+/// `recursive_verifier_vk`. This confirms every link in the chain validated
+/// its base proof, but — see [`aggregate_noir_proofs`]'s doc comment — does
+/// not close the chain's deferred aggregation object, so it is not a
+/// complete verification of the original base proofs on its own.
+#[uniffi::export]
+pub fn verify_aggregated_proof(
+    aggregated_proof: Vec<u8>,
+    recursive_verifier_vk: Vec<u8>,
+    on_chain: bool,
+    low_memory_mode: bool,
+) -> Result<bool, MoproError> {
+    verify_ultra_honk(aggregated_proof, recursive_verifier_vk, on_chain, low_memory_mode)
+        .map_err(|e| MoproError::NoirError(format!("Failed to verify aggregated proof: {}", e)))
+}
+
+/// Runs one link of the chain: natively verifies `proof` against `vk` (so a
+/// malformed base proof is rejected before spending a proving pass on a
+/// recursive verifier circuit that would reject it in-circuit anyway), then
+/// proves the recursive verifier circuit's witness — laid out as
+/// `vk ++ proof ++ public_inputs ++ key_hash ++ input_aggregation_object`,
+/// each field chunked to fit BN254, matching the positional argument order
+/// of `std::verify_proof(verification_key, proof, public_inputs, key_hash,
+/// input_aggregation_object)` — returning its proof and declared public
+/// output, which this chain treats as `output_aggregation_object` in full.
+#[allow(clippy::too_many_arguments)]
+fn recursively_verify(
+    circuit_path: &str,
+    circuit_vk: &[u8],
+    proof: &[u8],
+    vk: &[u8],
+    public_inputs: &[String],
+    input_aggregation_object: &[String],
+    on_chain: bool,
+    low_memory_mode: bool,
+) -> Result<(Vec<u8>, Vec<String>), MoproError> {
+    let valid = verify_ultra_honk(proof.to_vec(), vk.to_vec(), false, low_memory_mode)
+        .map_err(|e| MoproError::NoirError(format!("Base proof failed to verify: {}", e)))?;
+    if !valid {
+        return Err(MoproError::NoirError(
+            "base proof rejected by native verification before recursion".to_string(),
+        ));
+    }
+
+    let key_hash = hash_to_field_elements(vk)?;
+
+    let witness: Vec<String> = chunk_to_field_elements(vk)
+        .into_iter()
+        .chain(chunk_to_field_elements(proof))
+        .chain(public_inputs.iter().cloned())
+        .chain(key_hash)
+        .chain(input_aggregation_object.iter().cloned())
+        .collect();
+
+    let witness_map = noir_rs::witness::from_vec_str_to_witness_map(witness)
+        .map_err(|e| MoproError::NoirError(format!("Failed to build recursive witness: {}", e)))?;
+
+    let (inner_proof, output_aggregation_object) = prove_ultra_honk(
+        circuit_path,
+        witness_map,
+        circuit_vk.to_vec(),
+        on_chain,
+        low_memory_mode,
+    )
+    .map_err(|e| MoproError::NoirError(format!("Failed to generate recursive proof: {}", e)))?;
+
+    Ok((inner_proof, output_aggregation_object))
+}
+
+/// Splits `bytes` into 31-byte chunks, each small enough to fit under
+/// BN254's ~254-bit field modulus, and hex-encodes them as Noir field
+/// element inputs (the same format `noir_rs::witness` expects).
+fn chunk_to_field_elements(bytes: &[u8]) -> Vec<String> {
+    bytes
+        .chunks(31)
+        .map(|chunk| format!("0x{}", hex::encode(chunk)))
+        .collect()
+}
+
+/// Folds `bytes` through the crate's own BN254 Poseidon hash
+/// ([`poseidon_hash`]) in groups of up to 8 chunks at a time, Merkle-Damgård
+/// style, and returns the single-element digest as a one-entry field
+/// element list — a real `key_hash` over the whole of `bytes` (typically a
+/// verification key), not a truncation of it.
+fn hash_to_field_elements(bytes: &[u8]) -> Result<Vec<String>, MoproError> {
+    let chunks = chunk_to_field_elements(bytes);
+    let mut acc = vec![0u8; 32];
+    for group in chunks.chunks(8) {
+        let mut inputs = vec![acc];
+        for chunk in group {
+            let hex_str = chunk.strip_prefix("0x").unwrap_or(chunk);
+            inputs.push(
+                hex::decode(format!("{hex_str:0>64}"))
+                    .map_err(|e| MoproError::NoirError(format!("invalid chunk {chunk}: {e}")))?,
+            );
+        }
+        acc = poseidon_hash(inputs)?;
+    }
+    Ok(vec![format!("0x{}", hex::encode(acc))])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chunk_to_field_elements_splits_into_31_byte_words() {
+        let bytes = vec![0xAB; 65];
+        let chunks = chunk_to_field_elements(&bytes);
+
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks[0], format!("0x{}", hex::encode(vec![0xAB; 31])));
+        assert_eq!(chunks[1], format!("0x{}", hex::encode(vec![0xAB; 31])));
+        assert_eq!(chunks[2], format!("0x{}", hex::encode(vec![0xAB; 3])));
+    }
+
+    #[test]
+    fn chunk_to_field_elements_of_empty_input_is_empty() {
+        assert!(chunk_to_field_elements(&[]).is_empty());
+    }
+
+    #[test]
+    fn hash_to_field_elements_is_deterministic_and_sensitive_to_input() {
+        let a = hash_to_field_elements(b"hello").unwrap();
+        let b = hash_to_field_elements(b"hello").unwrap();
+        let c = hash_to_field_elements(b"hellp").unwrap();
+
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+        assert_eq!(a.len(), 1);
+    }
+}
@@ -0,0 +1,94 @@
+// Byte-oriented overloads of the path-based Noir functions in the parent
+// module.
+//
+// Mobile bindings don't have a stable filesystem layout to read a
+// `circuit_path` / `srs_path` from on every call, so these take the circuit
+// JSON (and, optionally, the SRS) as bytes instead and stage them to a
+// scratch file once per call. [`super::embedded`] builds on top of these to
+// bake the bytes in at compile time instead of passing them in at all.
+
+use std::io::Write;
+
+use tempfile::NamedTempFile;
+
+use crate::MoproError;
+
+/// Computes the UltraHonk verification key for `circuit_json`.
+///
+/// See [`super::get_noir_verification_key`] for the path-based version.
+#[uniffi::export]
+pub fn get_noir_verification_key_bytes(
+    circuit_json: Vec<u8>,
+    srs: Option<Vec<u8>>,
+    on_chain: bool,
+    low_memory_mode: bool,
+) -> Result<Vec<u8>, MoproError> {
+    let circuit_file = stage_bytes(&circuit_json)?;
+    let srs_file = srs.as_deref().map(stage_bytes).transpose()?;
+
+    super::get_noir_verification_key(
+        path_of(&circuit_file)?,
+        srs_file.as_ref().map(path_of).transpose()?,
+        on_chain,
+        low_memory_mode,
+    )
+}
+
+/// Generates an UltraHonk proof for `circuit_inputs` against `circuit_json`.
+///
+/// See [`super::generate_noir_proof`] for the path-based version.
+#[uniffi::export]
+pub fn generate_noir_proof_bytes(
+    circuit_json: Vec<u8>,
+    srs: Option<Vec<u8>>,
+    circuit_inputs: Vec<String>,
+    on_chain: bool,
+    vk: Vec<u8>,
+    low_memory_mode: bool,
+) -> Result<Vec<u8>, MoproError> {
+    let circuit_file = stage_bytes(&circuit_json)?;
+    let srs_file = srs.as_deref().map(stage_bytes).transpose()?;
+
+    super::generate_noir_proof(
+        path_of(&circuit_file)?,
+        srs_file.as_ref().map(path_of).transpose()?,
+        circuit_inputs,
+        on_chain,
+        vk,
+        low_memory_mode,
+    )
+}
+
+/// Verifies a proof previously produced by [`generate_noir_proof_bytes`].
+///
+/// See [`super::verify_noir_proof`] for the path-based version.
+#[uniffi::export]
+pub fn verify_noir_proof_bytes(
+    circuit_json: Vec<u8>,
+    proof: Vec<u8>,
+    on_chain: bool,
+    vk: Vec<u8>,
+    low_memory_mode: bool,
+) -> Result<bool, MoproError> {
+    let circuit_file = stage_bytes(&circuit_json)?;
+
+    super::verify_noir_proof(path_of(&circuit_file)?, proof, on_chain, vk, low_memory_mode)
+}
+
+/// Writes `bytes` to a scratch file that lives for the duration of the call
+/// that staged it, so the underlying Barretenberg bindings (which only take
+/// paths) can read it back.
+fn stage_bytes(bytes: &[u8]) -> Result<NamedTempFile, MoproError> {
+    let mut file = NamedTempFile::new()
+        .map_err(|e| MoproError::NoirError(format!("Failed to create scratch file: {}", e)))?;
+    file.write_all(bytes)
+        .map_err(|e| MoproError::NoirError(format!("Failed to write scratch file: {}", e)))?;
+    Ok(file)
+}
+
+fn path_of(file: &NamedTempFile) -> Result<String, MoproError> {
+    file.path()
+        .to_str()
+        .map(str::to_string)
+        .ok_or_else(|| MoproError::NoirError("scratch file path is not valid UTF-8".to_string()))
+}
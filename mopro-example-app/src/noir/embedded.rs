@@ -0,0 +1,71 @@
+// Build-time embedding of the Noir circuit/VK/SRS.
+//
+// When `build.rs` finds `MOPRO_NOIR_CIRCUIT_PATH` / `MOPRO_NOIR_VK_PATH` set,
+// it stages those files into `OUT_DIR` and sets the `noir_embedded` cfg,
+// which brings the constants below (and the functions wrapping
+// [`super::bytes`] around them) into the build. This produces a
+// self-contained library that proves and verifies without shipping loose
+// `.json` / `.vk` / `.srs` files to the device, the same way a Circom or
+// Halo2 circuit spec embedded into the artifact via `circom_stub!()` /
+// `halo2_stub!()` would be.
+
+#[cfg(noir_embedded)]
+static CIRCUIT_JSON: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/noir_circuit.json"));
+
+#[cfg(noir_embedded)]
+static VK: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/noir_circuit.vk"));
+
+// Gated on `noir_embedded` (not just `noir_srs_embedded`) like their sole
+// caller, `generate_noir_proof_embedded`, below: the SRS is only ever
+// embedded alongside the circuit/VK, never on its own, so gating it more
+// narrowly would make it (and `srs_bytes`) dead code in the default,
+// non-embedding build.
+#[cfg(noir_embedded)]
+fn srs_bytes() -> Option<Vec<u8>> {
+    #[cfg(noir_srs_embedded)]
+    {
+        static SRS: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/noir_circuit.srs"));
+        Some(SRS.to_vec())
+    }
+
+    #[cfg(not(noir_srs_embedded))]
+    {
+        None
+    }
+}
+
+/// Generates an UltraHonk proof using the circuit, VK and SRS baked into this
+/// build, so callers only need to supply `circuit_inputs`.
+#[cfg(noir_embedded)]
+#[uniffi::export]
+pub fn generate_noir_proof_embedded(
+    circuit_inputs: Vec<String>,
+    on_chain: bool,
+    low_memory_mode: bool,
+) -> Result<Vec<u8>, crate::MoproError> {
+    super::bytes::generate_noir_proof_bytes(
+        CIRCUIT_JSON.to_vec(),
+        srs_bytes(),
+        circuit_inputs,
+        on_chain,
+        VK.to_vec(),
+        low_memory_mode,
+    )
+}
+
+/// Verifies a proof against the circuit baked into this build.
+#[cfg(noir_embedded)]
+#[uniffi::export]
+pub fn verify_noir_proof_embedded(
+    proof: Vec<u8>,
+    on_chain: bool,
+    low_memory_mode: bool,
+) -> Result<bool, crate::MoproError> {
+    super::bytes::verify_noir_proof_bytes(
+        CIRCUIT_JSON.to_vec(),
+        proof,
+        on_chain,
+        VK.to_vec(),
+        low_memory_mode,
+    )
+}
@@ -0,0 +1,266 @@
+// On-chain verifier rendering for the Noir UltraHonk backend.
+//
+// Mirrors the `SolidityGenerator` pattern used by the other backends: the
+// verification key and the verifier logic are rendered as two separable
+// artifacts so a (potentially large) VK can be deployed once and reused.
+// Both artifacts are emitted into a single self-contained source string
+// (no cross-file `import`s) so the output compiles on its own without
+// shipping any companion `.sol` file alongside it.
+
+use sha3::{Digest, Keccak256};
+
+use crate::MoproError;
+
+/// Number of bytes per EVM word / calldata slot / BN254 field element.
+const WORD_SIZE: usize = 32;
+
+/// Renders a Solidity verifier for the UltraHonk circuit compiled at
+/// `circuit_path`, keyed by the (Keccak, `on_chain: true`) verification key
+/// `vk` produced by [`super::get_noir_verification_key`].
+///
+/// The output is a single self-contained Solidity source string containing:
+/// - a `NoirHonkVk` library holding the VK as packed `bytes32` constants
+///   plus a `vk()` accessor that reassembles them into `bytes memory`, and
+/// - a `Verifier` contract with a `verify(bytes proof, uint256[] instances)`
+///   entry point.
+///
+/// **`verify` is a placeholder and always reverts.** Checking an UltraHonk
+/// proof on-chain requires re-deriving its sumcheck/transcript challenges
+/// and the final batched KZG pairing over them — there is no way to check
+/// the proof against `instances` with only the final pairing, which is all
+/// this renderer lays out. Treat the rendered source as scaffolding (VK
+/// layout + calldata ABI) for linking against a real, audited UltraHonk
+/// Solidity verifier, not as a deployable verifier on its own.
+///
+/// `on_chain` must match the flag the VK/proof were generated with; a VK
+/// generated with the native (non-Keccak) transcript cannot be rendered as a
+/// Solidity verifier.
+#[uniffi::export]
+pub fn render_noir_solidity_verifier(
+    circuit_path: String,
+    vk: Vec<u8>,
+    on_chain: bool,
+) -> Result<String, MoproError> {
+    if !on_chain {
+        return Err(MoproError::NoirError(
+            "cannot render a Solidity verifier from a non-Keccak (on_chain: false) verification key"
+                .to_string(),
+        ));
+    }
+    if vk.len() < 2 * WORD_SIZE {
+        return Err(MoproError::NoirError(
+            "verification key is too short: expected at least two 32-byte words".to_string(),
+        ));
+    }
+
+    let vk_library = render_vk_library(&vk);
+    let verifier_contract = render_verifier_contract(&circuit_path);
+
+    Ok(format!("{vk_library}\n{verifier_contract}"))
+}
+
+/// Renders the VK as a standalone library of packed `bytes32` constants, one
+/// per word, plus a `vk()` accessor so it can be deployed once and linked by
+/// any verifier built from the same circuit.
+fn render_vk_library(vk: &[u8]) -> String {
+    let num_words = vk.len().div_ceil(WORD_SIZE);
+
+    let mut words = String::new();
+    for (i, chunk) in vk.chunks(WORD_SIZE).enumerate() {
+        let mut word = [0u8; WORD_SIZE];
+        word[..chunk.len()].copy_from_slice(chunk);
+        words.push_str(&format!(
+            "    bytes32 internal constant VK_{i} = 0x{};\n",
+            hex::encode(word)
+        ));
+    }
+
+    let mut concat = String::new();
+    for i in 0..num_words {
+        concat.push_str(&format!("VK_{i}"));
+        if i + 1 != num_words {
+            concat.push_str(", ");
+        }
+    }
+
+    format!(
+        "// SPDX-License-Identifier: MIT\n\
+         pragma solidity >=0.8.21;\n\n\
+         library NoirHonkVk {{\n\
+         {words}\n\
+         \x20   function vk() public pure returns (bytes memory) {{\n\
+         \x20       return abi.encodePacked({concat});\n\
+         \x20   }}\n\
+         }}\n"
+    )
+}
+
+/// Renders the verifier contract shell that reads `NoirHonkVk.vk()`.
+///
+/// `verify` is intentionally a stub that always reverts: checking an
+/// UltraHonk proof on-chain means re-deriving its sumcheck/transcript
+/// challenges from `proof` and `instances` and only then checking the final
+/// batched KZG pairing against `NoirHonkVk.vk()` — a single pairing check
+/// with no transcript behind it, which is all that can be laid out here,
+/// would accept any well-formed proof regardless of `instances` and must
+/// not be shipped as if it verified anything.
+fn render_verifier_contract(circuit_path: &str) -> String {
+    format!(
+        "// SPDX-License-Identifier: MIT\n\
+         pragma solidity >=0.8.21;\n\n\
+         /// Generated from {circuit_path} by mopro's Noir Solidity generator.\n\
+         ///\n\
+         /// `verify` is NOT implemented: a correct implementation must re-derive\n\
+         /// the UltraHonk sumcheck/transcript challenges from `proof` and\n\
+         /// `instances` before checking the final KZG pairing against\n\
+         /// `NoirHonkVk.vk()`. Link this against a real, audited UltraHonk\n\
+         /// Solidity verifier that performs that derivation; do not deploy this\n\
+         /// contract as-is.\n\
+         contract Verifier {{\n\
+         \x20   function verify(bytes calldata proof, uint256[] calldata instances)\n\
+         \x20       external\n\
+         \x20       view\n\
+         \x20       returns (bool)\n\
+         \x20   {{\n\
+         \x20       proof;\n\
+         \x20       instances;\n\
+         \x20       NoirHonkVk.vk();\n\
+         \x20       revert(\"Verifier.verify: not implemented, see contract doc comment\");\n\
+         \x20   }}\n\
+         }}\n"
+    )
+}
+
+/// ABI-encodes a call to `verify(bytes proof, uint256[] instances)`,
+/// prepending the function's 4-byte selector and laying out `proof`
+/// followed by the flattened `public_inputs` exactly as the contract
+/// rendered by [`render_noir_solidity_verifier`] expects to read them, so
+/// the result can be submitted directly via `eth_call`.
+///
+/// Each entry of `public_inputs` is a BN254 field element written as a
+/// `0x`-prefixed hex string, the same format `noir_rs::witness` expects
+/// elsewhere in this crate (see `aggregation::chunk_to_field_elements`).
+#[uniffi::export]
+pub fn encode_noir_calldata(proof: Vec<u8>, public_inputs: Vec<String>) -> Result<Vec<u8>, MoproError> {
+    let instances = public_inputs
+        .iter()
+        .map(|input| parse_field_word(input))
+        .collect::<Result<Vec<_>, MoproError>>()?;
+
+    let mut calldata = Vec::new();
+    calldata.extend_from_slice(&verify_selector());
+
+    // `bytes proof` head: offset to its dynamic data, fixed at two head
+    // slots (the `bytes` offset itself and the `uint256[]` offset).
+    let proof_offset = 2 * WORD_SIZE;
+    calldata.extend_from_slice(&encode_word(proof_offset as u128));
+
+    // `uint256[] instances` head: offset to its dynamic data, placed right
+    // after the proof's length-prefixed, word-padded bytes.
+    let proof_words = proof.len().div_ceil(WORD_SIZE);
+    let instances_offset = proof_offset + WORD_SIZE + proof_words * WORD_SIZE;
+    calldata.extend_from_slice(&encode_word(instances_offset as u128));
+
+    // `bytes proof` tail: length followed by the proof bytes, padded to a
+    // whole number of words.
+    calldata.extend_from_slice(&encode_word(proof.len() as u128));
+    calldata.extend_from_slice(&proof);
+    calldata.resize(calldata.len() + (proof_words * WORD_SIZE - proof.len()), 0);
+
+    // `uint256[] instances` tail: length followed by each flattened public
+    // input, in the order the verifier contract reads them.
+    calldata.extend_from_slice(&encode_word(instances.len() as u128));
+    for instance in instances {
+        calldata.extend_from_slice(&instance);
+    }
+
+    Ok(calldata)
+}
+
+/// The 4-byte selector for `verify(bytes,uint256[])`:
+/// `keccak256("verify(bytes,uint256[])")[..4]`.
+fn verify_selector() -> [u8; 4] {
+    let mut selector = [0u8; 4];
+    selector.copy_from_slice(&Keccak256::digest(b"verify(bytes,uint256[])")[..4]);
+    selector
+}
+
+fn encode_word(value: u128) -> [u8; WORD_SIZE] {
+    let mut word = [0u8; WORD_SIZE];
+    word[16..].copy_from_slice(&value.to_be_bytes());
+    word
+}
+
+/// Parses a `0x`-prefixed (or bare) hex string as a full 32-byte BN254 field
+/// element, left-padding with zeros. Field elements are up to ~254 bits, so
+/// this cannot use a fixed-width integer type like `u128`.
+fn parse_field_word(input: &str) -> Result<[u8; WORD_SIZE], MoproError> {
+    let hex_str = input.strip_prefix("0x").or_else(|| input.strip_prefix("0X")).unwrap_or(input);
+    if hex_str.len() > WORD_SIZE * 2 {
+        return Err(MoproError::NoirError(format!(
+            "public input {input} does not fit in a 32-byte field element"
+        )));
+    }
+
+    let padded = format!("{hex_str:0>64}");
+    let bytes = hex::decode(&padded)
+        .map_err(|e| MoproError::NoirError(format!("invalid public input {input}: {e}")))?;
+
+    let mut word = [0u8; WORD_SIZE];
+    word.copy_from_slice(&bytes);
+    Ok(word)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_field_word_left_pads_short_input() {
+        let word = parse_field_word("0x01").unwrap();
+        assert_eq!(word[..31], [0u8; 31]);
+        assert_eq!(word[31], 0x01);
+    }
+
+    #[test]
+    fn parse_field_word_rejects_oversized_input() {
+        let too_long = format!("0x{}", "ff".repeat(WORD_SIZE + 1));
+        assert!(parse_field_word(&too_long).is_err());
+    }
+
+    #[test]
+    fn parse_field_word_rejects_invalid_hex() {
+        assert!(parse_field_word("0xzz").is_err());
+    }
+
+    #[test]
+    fn verify_selector_matches_known_keccak_selector() {
+        // keccak256("verify(bytes,uint256[])")[..4]
+        assert_eq!(verify_selector(), [0x96, 0x49, 0xda, 0xae]);
+    }
+
+    #[test]
+    fn encode_noir_calldata_prepends_selector_and_encodes_args() {
+        let proof = vec![0xAB; 64];
+        let public_inputs = vec!["0x01".to_string(), "0x02".to_string()];
+
+        let calldata = encode_noir_calldata(proof.clone(), public_inputs).unwrap();
+
+        assert_eq!(&calldata[..4], &verify_selector());
+
+        let proof_offset = u128::from_be_bytes(calldata[4 + 16..4 + 32].try_into().unwrap()) as usize;
+        assert_eq!(proof_offset, 2 * WORD_SIZE);
+
+        let proof_len_start = 4 + proof_offset;
+        let proof_len = u128::from_be_bytes(
+            calldata[proof_len_start + 16..proof_len_start + 32]
+                .try_into()
+                .unwrap(),
+        ) as usize;
+        assert_eq!(proof_len, proof.len());
+        assert_eq!(
+            &calldata[proof_len_start + WORD_SIZE..proof_len_start + WORD_SIZE + proof.len()],
+            &proof[..]
+        );
+    }
+}
@@ -0,0 +1,264 @@
+// Incremental Poseidon Merkle tree, exposed over UniFFI as a handle-based
+// API (trees are long-lived and mutated in place, unlike the stateless
+// `noir` proving functions) so Semaphore/Tornado-style applications can
+// maintain a commitment tree and produce inclusion witnesses to feed as
+// inputs into `generate_noir_proof` / `generate_noir_proof_bytes`, instead
+// of computing the tree host-side in another language.
+
+use std::collections::HashMap;
+
+use ark_bn254::Fr;
+use ark_ff::{BigInteger, PrimeField};
+use light_poseidon::{Poseidon, PoseidonHasher};
+
+use crate::{
+    handle::{Handle, HandleRegistry},
+    MoproError,
+};
+
+/// A single incremental Merkle tree of `depth` levels over the BN254
+/// Poseidon hash, with cached zero-subtree hashes per level so that `set`
+/// only touches the O(depth) nodes on the path to the root.
+struct PoseidonTree {
+    depth: u32,
+    /// `zero_hashes[i]` is the root of a fully-zeroed subtree of height `i`,
+    /// with `zero_hashes[0]` being `zero_leaf` itself.
+    zero_hashes: Vec<Fr>,
+    /// Sparse storage of the nodes written by `set`, keyed by `(level, index)` with
+    /// `level` 0 at the leaves.
+    nodes: HashMap<(u32, u64), Fr>,
+}
+
+impl PoseidonTree {
+    fn new(depth: u32, zero_leaf: Fr) -> Self {
+        let mut zero_hashes = Vec::with_capacity(depth as usize + 1);
+        zero_hashes.push(zero_leaf);
+        for i in 0..depth {
+            let prev = zero_hashes[i as usize];
+            zero_hashes.push(hash2(prev, prev));
+        }
+
+        Self {
+            depth,
+            zero_hashes,
+            nodes: HashMap::new(),
+        }
+    }
+
+    /// Leaves are indexed `0..2^depth`; returns an error for anything
+    /// outside that range instead of silently wrapping or panicking.
+    fn check_index(&self, index: u64) -> Result<(), MoproError> {
+        let capacity = 1u64.checked_shl(self.depth).unwrap_or(u64::MAX);
+        if index >= capacity {
+            return Err(MoproError::NoirError(format!(
+                "leaf index {index} is out of range for a tree of depth {} (capacity {capacity})",
+                self.depth
+            )));
+        }
+        Ok(())
+    }
+
+    fn node(&self, level: u32, index: u64) -> Fr {
+        *self
+            .nodes
+            .get(&(level, index))
+            .unwrap_or(&self.zero_hashes[level as usize])
+    }
+
+    fn set(&mut self, index: u64, leaf: Fr) -> Result<(), MoproError> {
+        self.check_index(index)?;
+
+        self.nodes.insert((0, index), leaf);
+
+        let mut node = leaf;
+        let mut idx = index;
+        for level in 0..self.depth {
+            let sibling = self.node(level, idx ^ 1);
+            node = if idx % 2 == 0 {
+                hash2(node, sibling)
+            } else {
+                hash2(sibling, node)
+            };
+            idx /= 2;
+            self.nodes.insert((level + 1, idx), node);
+        }
+
+        Ok(())
+    }
+
+    fn root(&self) -> Fr {
+        self.node(self.depth, 0)
+    }
+
+    /// Returns the sibling hashes from the leaf up to (but not including)
+    /// the root, paired with the left/right bit for the leaf's own position
+    /// at each level (`false` = leaf is the left child).
+    fn proof(&self, index: u64) -> Result<(Vec<Fr>, Vec<bool>), MoproError> {
+        self.check_index(index)?;
+
+        let mut siblings = Vec::with_capacity(self.depth as usize);
+        let mut path_bits = Vec::with_capacity(self.depth as usize);
+
+        let mut idx = index;
+        for level in 0..self.depth {
+            siblings.push(self.node(level, idx ^ 1));
+            path_bits.push(idx % 2 != 0);
+            idx /= 2;
+        }
+
+        Ok((siblings, path_bits))
+    }
+}
+
+static TREES: HandleRegistry<PoseidonTree> = HandleRegistry::new();
+
+fn hash2(left: Fr, right: Fr) -> Fr {
+    let mut hasher = Poseidon::<Fr>::new_circom(2).expect("2-input Poseidon is supported");
+    hasher
+        .hash(&[left, right])
+        .expect("2-input Poseidon hash does not fail")
+}
+
+fn field_from_bytes(bytes: &[u8]) -> Result<Fr, MoproError> {
+    if bytes.len() > 32 {
+        return Err(MoproError::NoirError(
+            "poseidon input must be at most 32 bytes (a BN254 field element)".to_string(),
+        ));
+    }
+    let mut be = vec![0u8; 32 - bytes.len()];
+    be.extend_from_slice(bytes);
+    Ok(Fr::from_be_bytes_mod_order(&be))
+}
+
+fn bytes_from_field(value: Fr) -> Vec<u8> {
+    value.into_bigint().to_bytes_be()
+}
+
+/// Hashes `inputs` (each a big-endian, at-most-32-byte field element) with
+/// the BN254 Poseidon permutation.
+#[uniffi::export]
+pub fn poseidon_hash(inputs: Vec<Vec<u8>>) -> Result<Vec<u8>, MoproError> {
+    if inputs.is_empty() {
+        return Err(MoproError::NoirError(
+            "poseidon_hash requires at least one input".to_string(),
+        ));
+    }
+
+    let fields = inputs
+        .iter()
+        .map(|i| field_from_bytes(i))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut hasher = Poseidon::<Fr>::new_circom(fields.len())
+        .map_err(|e| MoproError::NoirError(format!("unsupported Poseidon arity: {}", e)))?;
+    let digest = hasher
+        .hash(&fields)
+        .map_err(|e| MoproError::NoirError(format!("Poseidon hash failed: {}", e)))?;
+
+    Ok(bytes_from_field(digest))
+}
+
+/// Creates a new incremental Poseidon Merkle tree of `depth` levels, with
+/// every leaf initialized to `zero_leaf`, and returns a handle to it.
+#[uniffi::export]
+pub fn poseidon_tree_new(depth: u32, zero_leaf: Vec<u8>) -> Result<Handle, MoproError> {
+    let zero_leaf = field_from_bytes(&zero_leaf)?;
+    let tree = PoseidonTree::new(depth, zero_leaf);
+    Ok(TREES.insert(tree))
+}
+
+/// Sets the leaf at `index` in the tree identified by `handle`, updating the
+/// O(depth) nodes on its path to the root.
+#[uniffi::export]
+pub fn poseidon_tree_set(handle: Handle, index: u64, leaf: Vec<u8>) -> Result<(), MoproError> {
+    let leaf = field_from_bytes(&leaf)?;
+    TREES.with_mut(handle, |tree| tree.set(index, leaf))
+}
+
+/// Returns the current root of the tree identified by `handle`.
+#[uniffi::export]
+pub fn poseidon_tree_root(handle: Handle) -> Result<Vec<u8>, MoproError> {
+    TREES.with(handle, |tree| Ok(bytes_from_field(tree.root())))
+}
+
+/// Returns the inclusion witness for `index` in the tree identified by
+/// `handle`: the sibling hashes from the leaf up to the root, and the
+/// matching left/right path bits (`false` = the leaf's node is the left
+/// child at that level).
+#[uniffi::export]
+pub fn poseidon_tree_proof(
+    handle: Handle,
+    index: u64,
+) -> Result<(Vec<Vec<u8>>, Vec<bool>), MoproError> {
+    TREES.with(handle, |tree| {
+        let (siblings, path_bits) = tree.proof(index)?;
+        Ok((siblings.into_iter().map(bytes_from_field).collect(), path_bits))
+    })
+}
+
+/// Frees the tree identified by `handle`. Callers that are done with a tree
+/// must call this, or its entry leaks in the process-wide registry for the
+/// rest of the process's lifetime.
+#[uniffi::export]
+pub fn poseidon_tree_free(handle: Handle) -> Result<(), MoproError> {
+    TREES.remove(handle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_tree_root_matches_recomputed_zero_hash() {
+        let depth = 3;
+        let zero_leaf = vec![0u8];
+        let tree = PoseidonTree::new(depth, field_from_bytes(&zero_leaf).unwrap());
+        assert_eq!(tree.root(), tree.zero_hashes[depth as usize]);
+    }
+
+    #[test]
+    fn set_changes_root_and_proof_verifies_against_it() {
+        let depth = 4;
+        let handle = poseidon_tree_new(depth, vec![0u8]).unwrap();
+        let before = poseidon_tree_root(handle).unwrap();
+
+        poseidon_tree_set(handle, 5, vec![0x42]).unwrap();
+        let after = poseidon_tree_root(handle).unwrap();
+        assert_ne!(before, after);
+
+        let (siblings, path_bits) = poseidon_tree_proof(handle, 5).unwrap();
+        assert_eq!(siblings.len(), depth as usize);
+        assert_eq!(path_bits.len(), depth as usize);
+
+        let mut node = field_from_bytes(&[0x42]).unwrap();
+        let mut idx = 5u64;
+        for (sibling, is_right) in siblings.into_iter().zip(path_bits) {
+            let sibling = field_from_bytes(&sibling).unwrap();
+            node = if is_right {
+                hash2(sibling, node)
+            } else {
+                hash2(node, sibling)
+            };
+            idx /= 2;
+        }
+        let _ = idx;
+        assert_eq!(bytes_from_field(node), after);
+
+        poseidon_tree_free(handle).unwrap();
+    }
+
+    #[test]
+    fn out_of_range_index_is_an_error_not_a_panic() {
+        let handle = poseidon_tree_new(2, vec![0u8]).unwrap();
+        assert!(poseidon_tree_set(handle, 4, vec![1]).is_err());
+        assert!(poseidon_tree_proof(handle, 4).is_err());
+    }
+
+    #[test]
+    fn free_invalidates_the_handle() {
+        let handle = poseidon_tree_new(2, vec![0u8]).unwrap();
+        poseidon_tree_free(handle).unwrap();
+        assert!(poseidon_tree_root(handle).is_err());
+        assert!(poseidon_tree_free(handle).is_err());
+    }
+}